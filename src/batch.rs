@@ -0,0 +1,266 @@
+//! Pixel batching for `draw_iter`, enabled via the `batch` feature.
+//!
+//! Streaming one RGB565 word per `set_window` + `WriteMemoryStart` wastes
+//! enormous time on an 800x480 (384,000-pixel) panel. This buffers pixels
+//! into a fixed-size on-stack array and, as long as consecutive pixels form
+//! a horizontal run (same row, increasing column), keeps appending to it
+//! instead of reopening the address window. A window update is only
+//! emitted when the run breaks, or when the buffer fills up, turning
+//! thousands of tiny transfers into a handful of large bursts.
+
+// `draw_batched` is only called from the `DrawTarget` impl in `graphics.rs`,
+// so `batch` is meaningless on its own; `Cargo.toml` should declare
+// `batch = ["graphics"]` so enabling one pulls in the other. Enforce that
+// here too, so a missing feature dependency fails loudly instead of leaving
+// `draw_batched` dead code under `-D warnings`.
+#[cfg(not(feature = "graphics"))]
+compile_error!("the `batch` feature requires the `graphics` feature to be enabled");
+
+use display_interface::WriteOnlyDataCommand;
+use embedded_graphics_core::{
+    pixelcolor::{raw::RawU16, Rgb565},
+    prelude::*,
+    Pixel,
+};
+use embedded_hal::{
+    blocking::delay::DelayMs,
+    digital::v2::{InputPin, OutputPin},
+};
+
+use crate::{Result, Rm68120};
+
+const BATCH_SIZE: usize = 512;
+
+impl<I, D, RST, TE> Rm68120<I, D, RST, TE>
+where
+    I: WriteOnlyDataCommand,
+    D: DelayMs<u32>,
+    RST: OutputPin,
+    TE: InputPin,
+{
+    // Draw `pixels`, coalescing consecutive pixels that form a horizontal
+    // run into a single `draw_raw_slice` call.
+    pub(crate) fn draw_batched<IT>(&mut self, pixels: IT) -> Result<()>
+    where
+        IT: IntoIterator<Item = Pixel<Rgb565>>,
+    {
+        let mut buffer = [0u16; BATCH_SIZE];
+        let mut len = 0;
+        // The run currently being buffered: (start_x, y, last_x).
+        let mut run: Option<(u16, u16, u16)> = None;
+
+        for Pixel(point, color) in pixels {
+            let x = point.x as u16;
+            let y = point.y as u16;
+            let color = RawU16::from(color).into_inner();
+
+            let continues_run = matches!(run, Some((_, ry, rx)) if ry == y && rx + 1 == x) && len < BATCH_SIZE;
+
+            if !continues_run {
+                self.flush_run(&mut run, &buffer[..len])?;
+                len = 0;
+            }
+
+            buffer[len] = color;
+            len += 1;
+
+            run = match run {
+                Some((start_x, _, _)) if continues_run => Some((start_x, y, x)),
+                _ => Some((x, y, x)),
+            };
+        }
+
+        self.flush_run(&mut run, &buffer[..len])
+    }
+
+    fn flush_run(&mut self, run: &mut Option<(u16, u16, u16)>, colors: &[u16]) -> Result<()> {
+        if let Some((start_x, y, end_x)) = run.take() {
+            self.draw_raw_slice(start_x, y, end_x, y, colors)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use display_interface::{DataFormat, DisplayError};
+    use embedded_graphics_core::{
+        geometry::Point,
+        pixelcolor::{raw::RawU16, Rgb565},
+        Pixel,
+    };
+    use embedded_hal::blocking::delay::DelayMs;
+
+    use super::{Rm68120, WriteOnlyDataCommand, BATCH_SIZE};
+    use crate::Command;
+
+    // Records every command word sent, and the data words streamed after
+    // `WriteMemoryStart` (the pixel colors, not the window bounds), so tests
+    // can assert on the exact sequence of `set_window`/`WriteMemoryStart`
+    // calls `draw_batched` produces without needing real hardware.
+    #[derive(Default)]
+    struct RecordingInterface {
+        commands: Vec<u16>,
+        data: Vec<u16>,
+    }
+
+    // A no-op delay, since none of this logic touches timing.
+    struct NoDelay;
+
+    impl DelayMs<u32> for NoDelay {
+        fn delay_ms(&mut self, _ms: u32) {}
+    }
+
+    impl WriteOnlyDataCommand for RecordingInterface {
+        fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+            match cmd {
+                DataFormat::U16BEIter(iter) => self.commands.extend(iter),
+                _ => unreachable!("this driver only ever sends U16BEIter commands"),
+            }
+            Ok(())
+        }
+
+        fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+            match buf {
+                DataFormat::U16BEIter(iter) => {
+                    if self.commands.last() == Some(&(Command::WriteMemoryStart as u16)) {
+                        self.data.extend(iter)
+                    }
+                }
+                _ => unreachable!("this driver only ever sends U16BEIter data"),
+            }
+            Ok(())
+        }
+    }
+
+    fn new_driver() -> Rm68120<RecordingInterface, NoDelay> {
+        Rm68120::new(
+            RecordingInterface::default(),
+            NoDelay,
+            None,
+            0,
+            0,
+            None,
+            320,
+            240,
+            crate::Orientation::Landscape,
+        )
+    }
+
+    fn pixel(x: i32, y: i32, color: u16) -> Pixel<Rgb565> {
+        Pixel(Point::new(x, y), Rgb565::from(RawU16::new(color)))
+    }
+
+    // Every `draw_raw_slice` call opens its own window, so one run should
+    // produce exactly one `SetColumnAddress`/`SetPageAddress`/
+    // `WriteMemoryStart` triple.
+    fn commands_sent(driver: &Rm68120<RecordingInterface, NoDelay>) -> Vec<u16> {
+        driver
+            .interface
+            .commands
+            .iter()
+            .copied()
+            .filter(|command| {
+                *command == Command::SetColumnAddress as u16
+                    || *command == Command::SetPageAddress as u16
+                    || *command == Command::WriteMemoryStart as u16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn coalesces_a_horizontal_run_into_one_window() {
+        let mut driver = new_driver();
+
+        driver
+            .draw_batched([pixel(0, 5, 1), pixel(1, 5, 2), pixel(2, 5, 3)])
+            .unwrap();
+
+        assert_eq!(
+            commands_sent(&driver),
+            [
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+            ]
+        );
+        assert_eq!(driver.interface.data, [1, 2, 3]);
+    }
+
+    #[test]
+    fn flushes_on_a_run_break() {
+        let mut driver = new_driver();
+
+        // Same row but a gap in x, then a different row: three separate runs.
+        driver
+            .draw_batched([pixel(0, 0, 1), pixel(5, 0, 2), pixel(5, 1, 3)])
+            .unwrap();
+
+        assert_eq!(
+            commands_sent(&driver),
+            [
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+            ]
+        );
+    }
+
+    #[test]
+    fn flushes_when_the_buffer_fills_up() {
+        let mut driver = new_driver();
+
+        // A single contiguous run exactly BATCH_SIZE long, plus one more
+        // pixel continuing the same run, must flush twice rather than
+        // overflowing the on-stack buffer.
+        let pixels = (0..=BATCH_SIZE as i32).map(|x| pixel(x, 0, x as u16));
+        driver.draw_batched(pixels).unwrap();
+
+        assert_eq!(
+            commands_sent(&driver),
+            [
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+            ]
+        );
+        assert_eq!(driver.interface.data.len(), BATCH_SIZE + 1);
+    }
+
+    #[test]
+    fn non_monotonic_pixels_each_open_their_own_window() {
+        let mut driver = new_driver();
+
+        // Decreasing x never continues a run, even on the same row.
+        driver
+            .draw_batched([pixel(5, 0, 1), pixel(4, 0, 2)])
+            .unwrap();
+
+        assert_eq!(
+            commands_sent(&driver),
+            [
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+                Command::SetColumnAddress as u16,
+                Command::SetPageAddress as u16,
+                Command::WriteMemoryStart as u16,
+            ]
+        );
+        assert_eq!(driver.interface.data, [1, 2]);
+    }
+}
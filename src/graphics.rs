@@ -0,0 +1,221 @@
+//! `embedded-graphics` integration, enabled via the `graphics` feature.
+
+use display_interface::WriteOnlyDataCommand;
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Size},
+    pixelcolor::{raw::RawU16, Rgb565},
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::{
+    blocking::delay::DelayMs,
+    digital::v2::{InputPin, OutputPin},
+};
+
+use crate::{Result, Rm68120};
+
+impl<I, D, RST, TE> OriginDimensions for Rm68120<I, D, RST, TE>
+where
+    I: WriteOnlyDataCommand,
+    D: DelayMs<u32>,
+    RST: OutputPin,
+    TE: InputPin,
+{
+    fn size(&self) -> Size {
+        Size::new(self.width as u32, self.height as u32)
+    }
+}
+
+impl<I, D, RST, TE> DrawTarget for Rm68120<I, D, RST, TE>
+where
+    I: WriteOnlyDataCommand,
+    D: DelayMs<u32>,
+    RST: OutputPin,
+    TE: InputPin,
+{
+    type Color = Rgb565;
+    type Error = display_interface::DisplayError;
+
+    #[cfg(not(feature = "batch"))]
+    fn draw_iter<IT>(&mut self, pixels: IT) -> Result<(), Self::Error>
+    where
+        IT: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+
+        for Pixel(point, color) in pixels {
+            if !bounds.contains(point) {
+                continue;
+            }
+
+            let x = point.x as u16;
+            let y = point.y as u16;
+
+            self.draw_raw_iter(x, y, x, y, [RawU16::from(color).into_inner()])?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "batch")]
+    fn draw_iter<IT>(&mut self, pixels: IT) -> Result<(), Self::Error>
+    where
+        IT: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        self.draw_batched(pixels.into_iter().filter(|p| bounds.contains(p.0)))
+    }
+
+    fn fill_contiguous<IT>(&mut self, area: &Rectangle, colors: IT) -> Result<(), Self::Error>
+    where
+        IT: IntoIterator<Item = Self::Color>,
+    {
+        let clipped = area.intersection(&self.bounding_box());
+
+        let Some(bottom_right) = clipped.bottom_right() else {
+            return Ok(());
+        };
+
+        // `colors` is a row-major stream over the *original* `area`, so points
+        // cropped out by the intersection above must be dropped from the stream
+        // to keep it aligned with the narrower window we're about to open.
+        let colors = area
+            .points()
+            .zip(colors)
+            .filter(|(point, _)| clipped.contains(*point))
+            .map(|(_, color)| RawU16::from(color).into_inner());
+
+        self.draw_raw_iter(
+            clipped.top_left.x as u16,
+            clipped.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+            colors,
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let count = (area.size.width * area.size.height) as usize;
+
+        self.fill_contiguous(&area, core::iter::repeat(color).take(count))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let bounds = self.bounding_box();
+        self.fill_solid(&bounds, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use display_interface::{DataFormat, DisplayError};
+    use embedded_graphics_core::geometry::Point;
+    use embedded_hal::blocking::delay::DelayMs;
+
+    use super::*;
+
+    // Records the data words streamed after `WriteMemoryStart` (skipping the
+    // window bounds written by `set_window`), so tests can assert on the
+    // exact color stream `fill_contiguous` hands to `draw_raw_iter` without
+    // needing real hardware.
+    #[derive(Default)]
+    struct RecordingInterface {
+        streaming_pixels: bool,
+        data: Vec<u16>,
+    }
+
+    impl WriteOnlyDataCommand for RecordingInterface {
+        fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+            match cmd {
+                DataFormat::U16BEIter(iter) => {
+                    self.streaming_pixels =
+                        iter.last() == Some(crate::Command::WriteMemoryStart as u16)
+                }
+                _ => unreachable!("this driver only ever sends U16BEIter commands"),
+            }
+            Ok(())
+        }
+
+        fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+            match buf {
+                DataFormat::U16BEIter(iter) => {
+                    if self.streaming_pixels {
+                        self.data.extend(iter)
+                    }
+                }
+                _ => unreachable!("this driver only ever sends U16BEIter data"),
+            }
+            Ok(())
+        }
+    }
+
+    // A no-op delay, since none of this logic touches timing.
+    struct NoDelay;
+
+    impl DelayMs<u32> for NoDelay {
+        fn delay_ms(&mut self, _ms: u32) {}
+    }
+
+    fn new_driver(width: usize, height: usize) -> Rm68120<RecordingInterface, NoDelay> {
+        Rm68120::new(
+            RecordingInterface::default(),
+            NoDelay,
+            None,
+            0,
+            0,
+            None,
+            width,
+            height,
+            crate::Orientation::Landscape,
+        )
+    }
+
+    fn color(value: u16) -> Rgb565 {
+        Rgb565::from(RawU16::new(value))
+    }
+
+    // `area` straddles the bottom-right edge of a 4x4 panel, so only the
+    // top-left 2x2 quadrant of it is actually on-screen. The color stream
+    // covers the full, unclipped 4x4 area in row-major order; the words
+    // written to the panel must be just the 4 colors that land inside the
+    // clipped 2x2 window, in the same row-major order, not the first 4
+    // colors of the unclipped stream.
+    #[test]
+    fn fill_contiguous_realigns_colors_to_the_clipped_window() {
+        let mut driver = new_driver(4, 4);
+        let area = Rectangle::new(Point::new(2, 2), Size::new(4, 4));
+        let colors = (0..16).map(color);
+
+        driver.fill_contiguous(&area, colors).unwrap();
+
+        assert_eq!(driver.interface.data, [0, 1, 4, 5]);
+    }
+
+    #[test]
+    fn fill_contiguous_sends_every_color_when_fully_on_screen() {
+        let mut driver = new_driver(4, 4);
+        let area = Rectangle::new(Point::new(0, 0), Size::new(2, 2));
+        let colors = (0..4).map(color);
+
+        driver.fill_contiguous(&area, colors).unwrap();
+
+        assert_eq!(driver.interface.data, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn fill_contiguous_sends_nothing_when_fully_off_screen() {
+        let mut driver = new_driver(4, 4);
+        let area = Rectangle::new(Point::new(10, 10), Size::new(2, 2));
+        let colors = (0..4).map(color);
+
+        driver.fill_contiguous(&area, colors).unwrap();
+
+        assert!(driver.interface.data.is_empty());
+    }
+}
@@ -1,10 +1,18 @@
 #![no_std]
 
+#[cfg(feature = "batch")]
+mod batch;
+#[cfg(feature = "graphics")]
+mod graphics;
+
 use core::iter::once;
 
 use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
 pub use display_interface_parallel_gpio::{Generic16BitBus, PGPIO16BitInterface};
-use embedded_hal::blocking::delay::DelayMs;
+use embedded_hal::{
+    blocking::delay::DelayMs,
+    digital::v2::{InputPin, OutputPin},
+};
 
 type Result<T = (), E = DisplayError> = core::result::Result<T, E>;
 
@@ -35,6 +43,32 @@ impl Orientation {
     }
 }
 
+// ---------------------------------------------------------------------------
+// CabcMode
+
+/// Content-adaptive backlight control mode, written via `SetCabcMode`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CabcMode {
+    Off          = 0x00,
+    Ui           = 0x01,
+    StillPicture = 0x02,
+    MovingImage  = 0x03,
+}
+
+// ---------------------------------------------------------------------------
+// TearingEffect
+
+/// Tearing-effect output mode, written via `SetTearOn`/`SetTearOff`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TearingEffect {
+    /// The TE pin is disabled.
+    Off,
+    /// The TE pin pulses once per frame, at V-blank.
+    VBlank,
+    /// The TE pin pulses at V-blank and at every H-blank.
+    VBlankAndHBlank,
+}
+
 // ---------------------------------------------------------------------------
 // Command
 
@@ -120,9 +154,69 @@ pub enum Command {
     ReadId3                    = 0xDC00,
 }
 
+// ---------------------------------------------------------------------------
+// ReadData
+
+/// A bidirectional 8080 bus capable of reading data back from the panel, in
+/// addition to the writes provided by [`WriteOnlyDataCommand`].
+///
+/// [`display-interface-parallel-gpio`](display_interface_parallel_gpio)'s
+/// [`PGPIO16BitInterface`] is write-only, so reading the many `Get*`/`Read*`
+/// commands requires a bus that switches its data lines to inputs; implement
+/// this trait for such a bus to unlock [`Rm68120`]'s read-back methods.
+pub trait ReadData {
+    /// Read a single 16-bit word.
+    fn read_u16(&mut self) -> Result<u16>;
+
+    /// Fill `buffer` with consecutively read 16-bit words.
+    fn read_slice(&mut self, buffer: &mut [u16]) -> Result<()> {
+        for word in buffer {
+            *word = self.read_u16()?;
+        }
+
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Driver
 
+/// A no-op stand-in for [`Rm68120`]'s reset pin, for panels wired without one.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoResetPin;
+
+impl OutputPin for NoResetPin {
+    type Error = core::convert::Infallible;
+
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+const DEFAULT_RESET_PRE_DELAY_MS: u32 = 10;
+const DEFAULT_RESET_POST_DELAY_MS: u32 = 120;
+
+/// A no-op stand-in for [`Rm68120`]'s tearing-effect pin, for panels wired
+/// without one.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoTearPin;
+
+impl InputPin for NoTearPin {
+    type Error = core::convert::Infallible;
+
+    fn is_high(&self) -> core::result::Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&self) -> core::result::Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
 // NOTE:
 //
 // The following pins are required in order to drive the display using 8080
@@ -133,23 +227,39 @@ pub enum Command {
 //   - data/command signal (LCD_DC/LCD_RS)
 //
 // These pins should be encapsulated by the `I` (for interface) generic type.
-pub struct Rm68120<I, D> {
+//
+// An optional reset pin (`RST`) may also be provided; panels wired without
+// one can use [`NoResetPin`], which is the default. Likewise, an optional
+// tearing-effect pin (`TE`) may be provided, defaulting to [`NoTearPin`].
+pub struct Rm68120<I, D, RST = NoResetPin, TE = NoTearPin> {
     interface: I,
     delay: D,
+    reset: Option<RST>,
+    reset_pre_delay_ms: u32,
+    reset_post_delay_ms: u32,
+    tearing_effect: Option<TE>,
     width: usize,
     height: usize,
     orientation: Orientation,
+    backlight_control_enabled: bool,
 }
 
-impl<I, D> Rm68120<I, D>
+impl<I, D, RST, TE> Rm68120<I, D, RST, TE>
 where
     I: WriteOnlyDataCommand,
     D: DelayMs<u32>,
+    RST: OutputPin,
+    TE: InputPin,
 {
     /// Construct the driver without any side effects
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         interface: I,
         delay: D,
+        reset: Option<RST>,
+        reset_pre_delay_ms: u32,
+        reset_post_delay_ms: u32,
+        tearing_effect: Option<TE>,
         width: usize,
         height: usize,
         orientation: Orientation,
@@ -157,12 +267,43 @@ where
         Self {
             interface,
             delay,
+            reset,
+            reset_pre_delay_ms,
+            reset_post_delay_ms,
+            tearing_effect,
             width,
             height,
             orientation,
+            backlight_control_enabled: false,
         }
     }
 
+    /// Bring the panel out of hardware reset and sleep, program the pixel
+    /// format and orientation, and turn the display on.
+    pub fn init(&mut self) -> Result<()> {
+        if let Some(reset) = self.reset.as_mut() {
+            reset.set_high().map_err(|_| DisplayError::RSError)?;
+            self.delay.delay_ms(self.reset_pre_delay_ms);
+            reset.set_low().map_err(|_| DisplayError::RSError)?;
+            self.delay.delay_ms(self.reset_pre_delay_ms);
+            reset.set_high().map_err(|_| DisplayError::RSError)?;
+            self.delay.delay_ms(self.reset_post_delay_ms);
+        }
+
+        self.command(Command::SoftReset)?;
+        self.delay.delay_ms(120);
+
+        self.command(Command::ExitSleepMode)?;
+        self.delay.delay_ms(120);
+
+        self.command(Command::SetPixelFormat)?;
+        self.write_iter([0x0055])?;
+
+        self.set_orientation(self.orientation)?;
+
+        self.command(Command::SetDisplayOn)
+    }
+
     /// Enable the display
     pub fn enable(&mut self) -> Result<()> {
         self.command(Command::SetDisplayOn)
@@ -173,11 +314,90 @@ where
         self.command(Command::SetDisplayOff)
     }
 
+    /// Set the backlight brightness, from `0` (off) to `255` (brightest).
+    ///
+    /// On first use this also writes `SetControlDisplay` to enable the
+    /// BCTRL/DD/BL bits, without which brightness writes have no effect.
+    pub fn set_brightness(&mut self, level: u8) -> Result<()> {
+        if !self.backlight_control_enabled {
+            self.command(Command::SetControlDisplay)?;
+            self.write_iter([CONTROL_DISPLAY_BCTRL_DD_BL as u16])?;
+            self.backlight_control_enabled = true;
+        }
+
+        self.command(Command::SetDisplayBrightness)?;
+        self.write_iter([level as u16])
+    }
+
+    /// Set the content-adaptive backlight control mode
+    pub fn set_cabc_mode(&mut self, mode: CabcMode) -> Result<()> {
+        self.command(Command::SetCabcMode)?;
+        self.write_iter([mode as u16])
+    }
+
+    /// Set the minimum brightness CABC is allowed to dim the backlight to
+    pub fn set_cabc_min_brightness(&mut self, level: u8) -> Result<()> {
+        self.command(Command::SetCabcMinBrightness)?;
+        self.write_iter([level as u16])
+    }
+
+    /// Set the tearing-effect output mode via `SetTearOn`/`SetTearOff`
+    pub fn set_tearing_effect(&mut self, mode: TearingEffect) -> Result<()> {
+        match mode {
+            TearingEffect::Off => self.command(Command::SetTearOff),
+            TearingEffect::VBlank => {
+                self.command(Command::SetTearOn)?;
+                self.write_iter([0x0000])
+            }
+            TearingEffect::VBlankAndHBlank => {
+                self.command(Command::SetTearOn)?;
+                self.write_iter([0x0001])
+            }
+        }
+    }
+
+    /// Block until the tearing-effect pin signals the start of the next
+    /// V-blank, for tear-free updates. Does nothing if no TE pin was
+    /// provided.
+    pub fn wait_for_vsync(&mut self) -> Result<()> {
+        if let Some(te) = self.tearing_effect.as_mut() {
+            // Wait out any pulse already in progress, then for the next rising edge.
+            while te.is_high().map_err(|_| DisplayError::RSError)? {}
+            while te.is_low().map_err(|_| DisplayError::RSError)? {}
+        }
+
+        Ok(())
+    }
+
     /// Get the current screen orientation
     pub fn orientation(&self) -> Orientation {
         self.orientation
     }
 
+    /// Program the RM68120 address-mode register (`SetAddressMode`) to
+    /// switch to `orientation`, swapping `width`/`height` if the new
+    /// orientation's axes differ from the current one.
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<()> {
+        let madctl: u8 = MADCTL_BGR
+            | match orientation {
+                Orientation::Landscape => MADCTL_MX | MADCTL_MV,
+                Orientation::LandscapeFlipped => MADCTL_MY | MADCTL_MV,
+                Orientation::Portrait => 0x00,
+                Orientation::PortraitFlipped => MADCTL_MX | MADCTL_MY,
+            };
+
+        self.command(Command::SetAddressMode)?;
+        self.write_iter([madctl as u16])?;
+
+        if self.orientation.is_landscape() != orientation.is_landscape() {
+            core::mem::swap(&mut self.width, &mut self.height);
+        }
+
+        self.orientation = orientation;
+
+        Ok(())
+    }
+
     /// Get the current screen width
     pub fn width(&self) -> usize {
         self.width
@@ -188,6 +408,38 @@ where
         self.height
     }
 
+    /// Open a rectangular address window via `SetColumnAddress`/`SetPageAddress`.
+    ///
+    /// The RM68120 expects each byte of the column/page bounds as its own
+    /// 16-bit word, so each `u16` coordinate is split into its high and low
+    /// bytes before being written.
+    pub fn set_window(&mut self, x0: u16, y0: u16, x1: u16, y1: u16) -> Result {
+        self.command(Command::SetColumnAddress)?;
+        self.write_iter(bytes_as_words(x0, x1))?;
+
+        self.command(Command::SetPageAddress)?;
+        self.write_iter(bytes_as_words(y0, y1))?;
+
+        Ok(())
+    }
+
+    /// Open the address window `(x0, y0)..=(x1, y1)` and stream `data` into it
+    /// as RGB565 words via `WriteMemoryStart`.
+    pub fn draw_raw_iter<IT>(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, data: IT) -> Result
+    where
+        IT: IntoIterator<Item = u16>,
+    {
+        self.set_window(x0, y0, x1, y1)?;
+        self.command(Command::WriteMemoryStart)?;
+        self.write_iter(data)
+    }
+
+    /// Convenience wrapper around [`Self::draw_raw_iter`] for callers that
+    /// already hold `data` as a contiguous `&[u16]` slice.
+    pub fn draw_raw_slice(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, data: &[u16]) -> Result {
+        self.draw_raw_iter(x0, y0, x1, y1, data.iter().copied())
+    }
+
     // PRIVATE FUNCTIONS
 
     fn command(&mut self, command: Command) -> Result {
@@ -201,7 +453,6 @@ where
     where
         IT: IntoIterator<Item = u16>,
     {
-        // FIXME: do I need to send a command first?
         self.interface
             .send_data(DataFormat::U16BEIter(&mut data.into_iter()))?;
 
@@ -209,6 +460,72 @@ where
     }
 }
 
+impl<I, D, RST, TE> Rm68120<I, D, RST, TE>
+where
+    I: WriteOnlyDataCommand + ReadData,
+    D: DelayMs<u32>,
+    RST: OutputPin,
+    TE: InputPin,
+{
+    // Per the RM68120 datasheet's read timing diagrams, every `Get*`/`Read*`
+    // command returns one leading dummy word before the real payload; each
+    // read below discards it first.
+
+    /// Read back the current power mode (`GetPowerMode`)
+    pub fn power_mode(&mut self) -> Result<u8> {
+        self.command(Command::GetPowerMode)?;
+        self.interface.read_u16()?; // dummy read
+        self.interface.read_u16().map(|word| word as u8)
+    }
+
+    /// Read back the 3-byte display ID (`GetDisplayId`)
+    pub fn display_id(&mut self) -> Result<[u8; 3]> {
+        self.command(Command::GetDisplayId)?;
+        self.interface.read_u16()?; // dummy read
+
+        let mut buffer = [0u16; 3];
+        self.interface.read_slice(&mut buffer)?;
+
+        Ok([buffer[0] as u8, buffer[1] as u8, buffer[2] as u8])
+    }
+
+    /// Read back the current scanline (`GetScanline`), for polling tear-free
+    /// update windows
+    pub fn scanline(&mut self) -> Result<u16> {
+        self.command(Command::GetScanline)?;
+        self.interface.read_u16()?; // dummy read
+        self.interface.read_u16()
+    }
+}
+
+// Bits of the RM68120 `SetAddressMode` (MADCTL) register used by
+// `set_orientation`.
+const MADCTL_MY: u8 = 0x80;
+const MADCTL_MX: u8 = 0x40;
+const MADCTL_MV: u8 = 0x20;
+
+// RM68120 panels are wired with BGR sub-pixel order, so this bit must be set
+// on every `SetAddressMode` write or red/blue channels come out swapped.
+// There's no known panel variant this driver targets that needs it cleared,
+// so it's folded into `madctl` unconditionally rather than exposed as a
+// setting.
+const MADCTL_BGR: u8 = 0x08;
+
+// `SetControlDisplay` bits enabling brightness control (BCTRL), the
+// display dimming curve (DD), and backlight control itself (BL).
+const CONTROL_DISPLAY_BCTRL_DD_BL: u8 = 0x20 | 0x08 | 0x04;
+
+// Split a pair of start/end coordinates into the four bytes expected by
+// `SetColumnAddress`/`SetPageAddress`, each widened to its own 16-bit word.
+fn bytes_as_words(start: u16, end: u16) -> [u16; 4] {
+    [
+        (start >> 8) as u16,
+        (start & 0xFF) as u16,
+        (end >> 8) as u16,
+        (end & 0xFF) as u16,
+    ]
+}
+
 // ---------------------------------------------------------------------------
 // Builder
 
@@ -219,6 +536,8 @@ pub struct Rm68120Builder {
     width: usize,
     height: usize,
     orientation: Orientation,
+    reset_pre_delay_ms: u32,
+    reset_post_delay_ms: u32,
 }
 
 impl Rm68120Builder {
@@ -228,6 +547,8 @@ impl Rm68120Builder {
             width: DEFAULT_WIDTH,
             height: DEFAULT_HEIGHT,
             orientation: Orientation::Landscape,
+            reset_pre_delay_ms: DEFAULT_RESET_PRE_DELAY_MS,
+            reset_post_delay_ms: DEFAULT_RESET_POST_DELAY_MS,
         }
     }
 
@@ -246,12 +567,41 @@ impl Rm68120Builder {
         self
     }
 
-    /// Construct the driver using the provided interface
-    pub fn build<I, D>(&self, interface: I, delay: D) -> Rm68120<I, D>
+    /// Set how long the reset pin is held low, and how long to wait after it
+    /// is released, during [`Rm68120::init`]
+    pub fn with_reset_delays(&mut self, pre_delay_ms: u32, post_delay_ms: u32) -> &mut Self {
+        self.reset_pre_delay_ms = pre_delay_ms;
+        self.reset_post_delay_ms = post_delay_ms;
+
+        self
+    }
+
+    /// Construct the driver using the provided interface, delay, and
+    /// optional reset and tearing-effect pins
+    #[allow(clippy::too_many_arguments)]
+    pub fn build<I, D, RST, TE>(
+        &self,
+        interface: I,
+        delay: D,
+        reset: Option<RST>,
+        tearing_effect: Option<TE>,
+    ) -> Rm68120<I, D, RST, TE>
     where
         I: WriteOnlyDataCommand,
         D: DelayMs<u32>,
+        RST: OutputPin,
+        TE: InputPin,
     {
-        Rm68120::new(interface, delay, self.width, self.height, self.orientation)
+        Rm68120::new(
+            interface,
+            delay,
+            reset,
+            self.reset_pre_delay_ms,
+            self.reset_post_delay_ms,
+            tearing_effect,
+            self.width,
+            self.height,
+            self.orientation,
+        )
     }
 }